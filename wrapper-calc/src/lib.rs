@@ -0,0 +1,47 @@
+use byte_calc::invoke_calculator;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+
+/// Instruction for this demo program: delegate a single binary calculator
+/// op to `byte_calc` via CPI, so the calculator's entrypoint gets
+/// exercised under cross-program invocation rather than only as a
+/// top-level instruction.
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct WrapperInstruction {
+    pub op: u8,
+    pub left: i64,
+    pub right: i64,
+}
+
+entrypoint!(process_instruction);
+
+pub fn process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let instruction = WrapperInstruction::try_from_slice(instruction_data)
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    let account_info_iter = &mut accounts.iter();
+    let calculator_program = next_account_info(account_info_iter)?;
+    let result_account = next_account_info(account_info_iter).ok();
+
+    msg!("Delegating op {} to byte_calc via CPI", instruction.op);
+
+    invoke_calculator(
+        calculator_program.key,
+        instruction.op,
+        instruction.left,
+        instruction.right,
+        result_account.map(|account| account.key),
+        accounts,
+    )
+}